@@ -1,22 +1,216 @@
+use std::collections::BTreeSet;
 use std::marker::PhantomData;
 
+use group::prime::PrimeGroup;
 use halo2_proofs::{
     arithmetic::FieldExt,
-    circuit::{Layouter, floor_planner::V1, Value},
+    circuit::{AssignedCell, Layouter, floor_planner::V1, Value},
+    dev::CircuitCost,
     plonk::*,
     poly::Rotation,
 };
 
+/// Bits reserved for each tiebreak rank inside a packed `hand_score`, and
+/// the resulting shift applied to the category so the two never overlap.
+const TIEBREAK_BITS_PER_RANK: u32 = 4;
+
+/// Category weights packed into the high bits of `hand_score`, in
+/// increasing rank order. The `hand_score` gate keeps these mutually
+/// exclusive (a straight flush contributes `W_STRAIGHT_FLUSH`, not
+/// `W_STRAIGHT + W_FLUSH`), so the packed value totally orders hands.
+const W_ONE_PAIR: u64 = 1;
+const W_TWO_PAIR: u64 = 2;
+const W_THREE_OF_A_KIND: u64 = 3;
+const W_STRAIGHT: u64 = 4;
+const W_FLUSH: u64 = 5;
+const W_FULL_HOUSE: u64 = 6;
+const W_FOUR_OF_A_KIND: u64 = 7;
+const W_STRAIGHT_FLUSH: u64 = 8;
+const W_ROYAL_FLUSH: u64 = 9;
+
+/// Which hole/board sizes a `VanillaHoldemCircuit` is configured for.
+///
+/// `hand_size` must either equal `n_hole + n_board` (the cards are used
+/// as-is, e.g. [`HoldemParams::five_card_exact`]) or `select_best_hand`
+/// must recognize the `(n_hole, pool.len())` combination: `n_hole == 2`
+/// with a 7-card pool brute-forces Texas Hold'em's best-5-of-7 (no
+/// constraint on how many hole cards are used), and `n_hole == 4` with a
+/// 9-card pool brute-forces Omaha's "must use exactly 2 hole cards" rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HoldemParams {
+    pub n_hole: usize,
+    pub n_board: usize,
+    pub hand_size: usize,
+    /// If set, the circuit constrains exactly this many of the `n_hole`
+    /// pool positions to be chosen into the final hand (Omaha's "must use
+    /// exactly 2 hole cards" rule). `None` leaves the hole/board split of
+    /// the chosen hand unconstrained beyond the total `hand_size` (Texas
+    /// Hold'em: any of the 0..=n_hole hole cards may be used).
+    pub required_hole_cards: Option<usize>,
+}
+
+impl HoldemParams {
+    /// 2 hole cards + 5 community cards, best 5 of 7.
+    pub fn texas_holdem() -> Self {
+        Self { n_hole: 2, n_board: 5, hand_size: 5, required_hole_cards: None }
+    }
+
+    /// 4 hole cards + 5 community cards; every hand must use exactly 2 of
+    /// the 4 hole cards and exactly 3 of the board.
+    pub fn omaha() -> Self {
+        Self { n_hole: 4, n_board: 5, hand_size: 5, required_hole_cards: Some(2) }
+    }
+
+    /// 2 hole cards + 3 community cards, used as-is with no best-of-N
+    /// selection. Not real short-deck hold'em rules (that removes ranks
+    /// 2-5 from the deck and reorders flush vs. full house); this is
+    /// just the smallest pool that exercises `assign_card`'s
+    /// `pool.len() == hand_size` path.
+    pub fn five_card_exact() -> Self {
+        Self { n_hole: 2, n_board: 3, hand_size: 5, required_hole_cards: None }
+    }
+}
+
+impl Default for HoldemParams {
+    fn default() -> Self {
+        Self::texas_holdem()
+    }
+}
+
+/// Fixed lookup table enumerating the 52 valid cards together with their
+/// `(rank, suit)` decomposition. `card` holds the raw card index (0..52,
+/// the same representation the advice `cards` columns witness), `rank`
+/// holds `card % 13` and `suit` holds `card / 13`.
+#[derive(Debug, Clone, Copy)]
+pub struct CardTableConfig {
+    pub card: TableColumn,
+    pub rank: TableColumn,
+    pub suit: TableColumn,
+}
+
+impl CardTableConfig {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            card: meta.lookup_table_column(),
+            rank: meta.lookup_table_column(),
+            suit: meta.lookup_table_column(),
+        }
+    }
+
+    pub fn load<F: FieldExt>(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "card rank/suit table",
+            |mut table| {
+                for card_index in 0..52usize {
+                    table.assign_cell(
+                        || "card",
+                        self.card,
+                        card_index,
+                        || Value::known(F::from(card_index as u64)),
+                    )?;
+                    table.assign_cell(
+                        || "rank",
+                        self.rank,
+                        card_index,
+                        || Value::known(F::from((card_index % 13) as u64)),
+                    )?;
+                    table.assign_cell(
+                        || "suit",
+                        self.suit,
+                        card_index,
+                        || Value::known(F::from((card_index / 13) as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Small range table over `0..13`, used to bound the gaps between
+/// consecutive sorted ranks so the monotonicity check can't wrap around
+/// the field.
+#[derive(Debug, Clone, Copy)]
+pub struct RangeTableConfig {
+    pub range: TableColumn,
+}
+
+impl RangeTableConfig {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self { range: meta.lookup_table_column() }
+    }
+
+    pub fn load<F: FieldExt>(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "rank gap range table",
+            |mut table| {
+                for i in 0..13usize {
+                    table.assign_cell(|| "range", self.range, i, || Value::known(F::from(i as u64)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Range table over `0..52`, used to bound the gaps between consecutive
+/// entries of `sorted_pool`. Combined with the grand-product argument
+/// tying `sorted_pool` back to `pool_cards` (see "sorted_pool is a
+/// permutation of pool_cards"), a strict (not just non-decreasing) gap
+/// here proves the pool holds no duplicate card: a prover who names the
+/// same physical card twice can't sort it into two strictly-increasing
+/// slots.
+#[derive(Debug, Clone, Copy)]
+pub struct CardGapTableConfig {
+    pub gap: TableColumn,
+}
+
+impl CardGapTableConfig {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self { gap: meta.lookup_table_column() }
+    }
+
+    pub fn load<F: FieldExt>(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "pool card gap range table",
+            |mut table| {
+                for i in 0..52usize {
+                    table.assign_cell(|| "gap", self.gap, i, || Value::known(F::from(i as u64)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct VanillaHoldemConfig {
-    pub q_straight: Selector,
-    pub q_flush: Selector,
-    pub q_one_pair: Selector,
-    pub q_two_pair: Selector,
-    pub q_three_of_a_kind: Selector,
-    pub q_four_of_a_kind: Selector,
-    pub cards: [Column<Advice>; 5],
-    pub table_cards: [Column<Instance>; 2],
+    pub params: HoldemParams,
+    pub q_sorted: Selector,
+    pub cards: Vec<Column<Advice>>,
+    pub ranks: Vec<Column<Advice>>,
+    pub suits: Vec<Column<Advice>>,
+    pub sorted: Vec<Column<Advice>>,
+    pub eq: Vec<Column<Advice>>,
+    pub eq_inv: Vec<Column<Advice>>,
+    pub straight_inv: Vec<Column<Advice>>,
+    pub flush_inv: Vec<Column<Advice>>,
+    pub is_wheel: Column<Advice>,
+    pub ace_high_inv: Column<Advice>,
+    pub perm_prod: Vec<Column<Advice>>,
+    pub pool_cards: Vec<Column<Advice>>,
+    pub sorted_pool: Vec<Column<Advice>>,
+    pub pool_perm_prod: Vec<Column<Advice>>,
+    pub selected: Vec<Column<Advice>>,
+    pub pool_prod: Vec<Column<Advice>>,
+    pub hand_prod: Vec<Column<Advice>>,
+    pub hand_score: Column<Advice>,
+    pub gamma: Challenge,
+    pub table_cards: Vec<Column<Instance>>,
+    pub hand_score_instance: Column<Instance>,
+    pub card_table: CardTableConfig,
+    pub range_table: RangeTableConfig,
+    pub card_gap_table: CardGapTableConfig,
 }
 
 struct VanillaHoldemChip<F: FieldExt> {
@@ -28,198 +222,1783 @@ impl<F: FieldExt> VanillaHoldemChip<F> {
     pub fn construct(config: VanillaHoldemConfig) -> Self {
         Self { config, _marker: PhantomData }
     }
+
+    /// Builds a fresh set of hand-evaluation columns for `params` against
+    /// an already-configured `card_table`/`range_table`/`card_gap_table`,
+    /// and wires up the hand-evaluation gates over them. Split out from
+    /// `VanillaHoldemCircuit::configure_with_params` so multi-hand
+    /// circuits (e.g. a showdown comparing two players) can share a
+    /// single set of lookup tables instead of loading one per hand.
+    pub fn configure_columns(
+        meta: &mut ConstraintSystem<F>,
+        params: HoldemParams,
+        card_table: CardTableConfig,
+        range_table: RangeTableConfig,
+        card_gap_table: CardGapTableConfig,
+    ) -> VanillaHoldemConfig {
+        assert_eq!(
+            params.hand_size, 5,
+            "the hand_score gate hardcodes 5-card categories (straight/flush/pairs) and tiebreak slots; hand_size must be 5"
+        );
+        let hand_size = params.hand_size;
+        let n_total = params.n_hole + params.n_board;
+
+        let q_sorted = meta.selector();
+
+        let cards = (0..hand_size).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let ranks = (0..hand_size).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let suits = (0..hand_size).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let sorted = (0..hand_size).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let eq = (0..hand_size - 1).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let eq_inv = (0..hand_size - 1).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let straight_inv = (0..hand_size - 1).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let flush_inv = (0..hand_size - 1).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let is_wheel = meta.advice_column();
+        let ace_high_inv = meta.advice_column();
+
+        let pool_cards = (0..n_total).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        for &pool_card in pool_cards.iter().skip(params.n_hole) {
+            meta.enable_equality(pool_card);
+        }
+        let sorted_pool = (0..n_total).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let selected = (0..n_total).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let hand_score = meta.advice_column();
+        meta.enable_equality(hand_score);
+
+        let gamma = meta.challenge_usable_after(FirstPhase);
+        let perm_prod = (0..hand_size).map(|_| meta.advice_column_in(SecondPhase)).collect::<Vec<_>>();
+        let pool_perm_prod = (0..n_total).map(|_| meta.advice_column_in(SecondPhase)).collect::<Vec<_>>();
+        let pool_prod = (0..n_total).map(|_| meta.advice_column_in(SecondPhase)).collect::<Vec<_>>();
+        let hand_prod = (0..hand_size).map(|_| meta.advice_column_in(SecondPhase)).collect::<Vec<_>>();
+
+        let table_cards = (0..params.n_board).map(|_| meta.instance_column()).collect::<Vec<_>>();
+        let hand_score_instance = meta.instance_column();
+
+        Self::configure(meta,
+            params,
+            q_sorted,
+            cards,
+            ranks,
+            suits,
+            sorted,
+            eq,
+            eq_inv,
+            straight_inv,
+            flush_inv,
+            is_wheel,
+            ace_high_inv,
+            perm_prod,
+            pool_cards,
+            sorted_pool,
+            pool_perm_prod,
+            selected,
+            pool_prod,
+            hand_prod,
+            hand_score,
+            gamma,
+            table_cards,
+            hand_score_instance,
+            card_table,
+            range_table,
+            card_gap_table,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn configure(meta: &mut ConstraintSystem<F>,
-        q_flush: Selector,
-        q_straight: Selector,
-        q_one_pair: Selector,
-        q_two_pair: Selector,
-        q_three_of_a_kind: Selector,
-        q_four_of_a_kind: Selector,
-        cards: [Column<Advice>; 5],
-        table_cards: [Column<Instance>; 2],
-        num_of_pair: Column<Advice>,
-        num_of_same_kind: Column<Advice>,
+        params: HoldemParams,
+        q_sorted: Selector,
+        cards: Vec<Column<Advice>>,
+        ranks: Vec<Column<Advice>>,
+        suits: Vec<Column<Advice>>,
+        sorted: Vec<Column<Advice>>,
+        eq: Vec<Column<Advice>>,
+        eq_inv: Vec<Column<Advice>>,
+        straight_inv: Vec<Column<Advice>>,
+        flush_inv: Vec<Column<Advice>>,
+        is_wheel: Column<Advice>,
+        ace_high_inv: Column<Advice>,
+        perm_prod: Vec<Column<Advice>>,
+        pool_cards: Vec<Column<Advice>>,
+        sorted_pool: Vec<Column<Advice>>,
+        pool_perm_prod: Vec<Column<Advice>>,
+        selected: Vec<Column<Advice>>,
+        pool_prod: Vec<Column<Advice>>,
+        hand_prod: Vec<Column<Advice>>,
+        hand_score: Column<Advice>,
+        gamma: Challenge,
+        table_cards: Vec<Column<Instance>>,
+        hand_score_instance: Column<Instance>,
+        card_table: CardTableConfig,
+        range_table: RangeTableConfig,
+        card_gap_table: CardGapTableConfig,
     ) -> VanillaHoldemConfig {
-        meta.create_gate("straight", |meta| {
-            let q_straight = meta.query_selector(q_straight);
+        let hand_size = params.hand_size;
+        let n_total = pool_cards.len();
+
+        for i in 0..hand_size {
+            meta.lookup("card is valid and decomposes into (rank, suit)", |meta| {
+                let card = meta.query_advice(cards[i], Rotation::cur());
+                let rank = meta.query_advice(ranks[i], Rotation::cur());
+                let suit = meta.query_advice(suits[i], Rotation::cur());
+
+                vec![
+                    (card, card_table.card),
+                    (rank, card_table.rank),
+                    (suit, card_table.suit),
+                ]
+            });
+        }
+
+        for i in 0..n_total {
+            meta.lookup("pooled card is a valid card index", |meta| {
+                let card = meta.query_advice(pool_cards[i], Rotation::cur());
+                vec![(card, card_table.card)]
+            });
+        }
+
+        for i in 0..n_total - 1 {
+            meta.lookup("pool cards are strictly increasing once sorted", |meta| {
+                let q_sorted = meta.query_selector(q_sorted);
+                let cur = meta.query_advice(sorted_pool[i], Rotation::cur());
+                let next = meta.query_advice(sorted_pool[i + 1], Rotation::cur());
+
+                vec![(q_sorted * (next - cur - Expression::Constant(F::one())), card_gap_table.gap)]
+            });
+        }
+
+        meta.create_gate("sorted_pool is a permutation of pool_cards", |meta| {
+            let q_sorted = meta.query_selector(q_sorted);
+            let gamma = meta.query_challenge(gamma);
+
             let mut constraints = vec![];
 
-            for i in 1..5 {
-                // fix rotation
-                let diff = meta.query_advice(cards[i], Rotation::cur());
+            let pool0 = meta.query_advice(pool_cards[0], Rotation::cur());
+            let sorted_pool0 = meta.query_advice(sorted_pool[0], Rotation::cur());
+            let prod0 = meta.query_advice(pool_perm_prod[0], Rotation::cur());
+            constraints.push(
+                q_sorted.clone() * (prod0.clone() * (sorted_pool0 + gamma.clone()) - (pool0 + gamma.clone())),
+            );
+
+            for i in 1..n_total {
+                let pool_i = meta.query_advice(pool_cards[i], Rotation::cur());
+                let sorted_pool_i = meta.query_advice(sorted_pool[i], Rotation::cur());
+                let prod_prev = meta.query_advice(pool_perm_prod[i - 1], Rotation::cur());
+                let prod_i = meta.query_advice(pool_perm_prod[i], Rotation::cur());
 
-                constraints.push(q_straight.clone() * (diff.clone() - Expression::Constant(F::one())));
+                constraints.push(
+                    q_sorted.clone()
+                        * (prod_i * (sorted_pool_i + gamma.clone()) - prod_prev * (pool_i + gamma.clone())),
+                );
             }
-            
+
+            let prod_last = meta.query_advice(pool_perm_prod[n_total - 1], Rotation::cur());
+            constraints.push(q_sorted * (prod_last - Expression::Constant(F::one())));
+
             constraints
         });
 
-        meta.create_gate("flush", |meta| {
-            let q_flush = meta.query_selector(q_flush);
+        for i in 0..hand_size - 1 {
+            meta.lookup("sorted ranks are non-decreasing by a bounded step", |meta| {
+                let q_sorted = meta.query_selector(q_sorted);
+                let cur = meta.query_advice(sorted[i], Rotation::cur());
+                let next = meta.query_advice(sorted[i + 1], Rotation::cur());
+
+                vec![(q_sorted * (next - cur), range_table.range)]
+            });
+        }
+
+        meta.create_gate("sorted is a permutation of ranks", |meta| {
+            let q_sorted = meta.query_selector(q_sorted);
+            let gamma = meta.query_challenge(gamma);
+
             let mut constraints = vec![];
-            
-            for i in 0..4 {
-                // fix rotation
-                let cur = meta.query_advice(cards[i], Rotation::cur());
-                let next = meta.query_advice(cards[i + 1], Rotation::cur());
-
-                let diff = cur - next;
-                constraints.push(q_flush.clone() * diff);
+
+            let rank0 = meta.query_advice(ranks[0], Rotation::cur());
+            let sorted0 = meta.query_advice(sorted[0], Rotation::cur());
+            let prod0 = meta.query_advice(perm_prod[0], Rotation::cur());
+            constraints.push(
+                q_sorted.clone() * (prod0.clone() * (sorted0 + gamma.clone()) - (rank0 + gamma.clone())),
+            );
+
+            for i in 1..hand_size {
+                let rank_i = meta.query_advice(ranks[i], Rotation::cur());
+                let sorted_i = meta.query_advice(sorted[i], Rotation::cur());
+                let prod_prev = meta.query_advice(perm_prod[i - 1], Rotation::cur());
+                let prod_i = meta.query_advice(perm_prod[i], Rotation::cur());
+
+                constraints.push(
+                    q_sorted.clone()
+                        * (prod_i * (sorted_i + gamma.clone()) - prod_prev * (rank_i + gamma.clone())),
+                );
             }
+
+            let prod_last = meta.query_advice(perm_prod[hand_size - 1], Rotation::cur());
+            constraints.push(q_sorted * (prod_last - Expression::Constant(F::one())));
+
             constraints
         });
 
-        meta.create_gate("one pair", |meta| {
-            let q_one_pair = meta.query_selector(q_one_pair);
-
+        meta.create_gate("adjacency of sorted ranks", |meta| {
+            let q_sorted = meta.query_selector(q_sorted);
             let mut constraints = vec![];
-            let num_of_pair = meta.query_advice(num_of_pair, Rotation::cur());
 
-            constraints.push(q_one_pair * (Expression::Constant(F::one()) - num_of_pair));
+            for i in 0..hand_size - 1 {
+                let cur = meta.query_advice(sorted[i], Rotation::cur());
+                let next = meta.query_advice(sorted[i + 1], Rotation::cur());
+                let diff = next - cur;
+                let eq_i = meta.query_advice(eq[i], Rotation::cur());
+                let eq_inv_i = meta.query_advice(eq_inv[i], Rotation::cur());
+
+                // eq_i is the standard is_zero(diff) witness: eq_i == 1 iff diff == 0.
+                constraints.push(
+                    q_sorted.clone()
+                        * (eq_i.clone() - (Expression::Constant(F::one()) - diff.clone() * eq_inv_i)),
+                );
+                constraints.push(q_sorted.clone() * (diff * eq_i));
+            }
 
             constraints
         });
 
-        meta.create_gate("two pair", |meta| {
-            let q_two_pair = meta.query_selector(q_two_pair);
-
+        meta.create_gate("selection bits are boolean and pick hand_size cards", |meta| {
+            let q_sorted = meta.query_selector(q_sorted);
             let mut constraints = vec![];
-            let num_of_pair = meta.query_advice(num_of_pair, Rotation::cur());
 
-            constraints.push(q_two_pair * (Expression::Constant(F::from_u128(2)) - num_of_pair));
+            let mut sum = Expression::Constant(F::zero());
+            for i in 0..n_total {
+                let selected_i = meta.query_advice(selected[i], Rotation::cur());
+                constraints.push(q_sorted.clone() * (selected_i.clone() * (Expression::Constant(F::one()) - selected_i.clone())));
+                sum = sum + selected_i;
+            }
+            constraints.push(q_sorted * (sum - Expression::Constant(F::from_u128(hand_size as u128))));
 
             constraints
         });
 
-        meta.create_gate("three of a kind", |meta| {
-            let q_three_of_a_kind = meta.query_selector(q_three_of_a_kind);
+        if let Some(required_hole_cards) = params.required_hole_cards {
+            meta.create_gate("selection uses exactly required_hole_cards of the hole cards", |meta| {
+                let q_sorted = meta.query_selector(q_sorted);
+
+                let mut hole_sum = Expression::Constant(F::zero());
+                for &selected_col in selected.iter().take(params.n_hole) {
+                    hole_sum = hole_sum + meta.query_advice(selected_col, Rotation::cur());
+                }
+
+                vec![q_sorted * (hole_sum - Expression::Constant(F::from_u128(required_hole_cards as u128)))]
+            });
+        }
+
+        meta.create_gate("chosen hand is a selected sub-multiset of the pool", |meta| {
+            let q_sorted = meta.query_selector(q_sorted);
+            let gamma = meta.query_challenge(gamma);
 
             let mut constraints = vec![];
-            let num_of_same_kind = meta.query_advice(num_of_same_kind, Rotation::cur());
 
-            constraints.push(q_three_of_a_kind * (Expression::Constant(F::from_u128(3)) - num_of_same_kind));
+            let selected0 = meta.query_advice(selected[0], Rotation::cur());
+            let pool0 = meta.query_advice(pool_cards[0], Rotation::cur());
+            let pool_prod0 = meta.query_advice(pool_prod[0], Rotation::cur());
+            let term0 = selected0.clone() * (pool0 + gamma.clone()) + (Expression::Constant(F::one()) - selected0);
+            constraints.push(q_sorted.clone() * (pool_prod0 - term0));
 
-            constraints
-        });
+            for i in 1..n_total {
+                let selected_i = meta.query_advice(selected[i], Rotation::cur());
+                let pool_i = meta.query_advice(pool_cards[i], Rotation::cur());
+                let term_i = selected_i.clone() * (pool_i + gamma.clone()) + (Expression::Constant(F::one()) - selected_i);
 
-        meta.create_gate("four of a kind", |meta| {
-            let q_four_of_a_kind = meta.query_selector(q_four_of_a_kind);
+                let prod_prev = meta.query_advice(pool_prod[i - 1], Rotation::cur());
+                let prod_i = meta.query_advice(pool_prod[i], Rotation::cur());
+                constraints.push(q_sorted.clone() * (prod_i - prod_prev * term_i));
+            }
 
-            let mut constraints = vec![];
-            let num_of_same_kind = meta.query_advice(num_of_same_kind, Rotation::cur());
+            let card0 = meta.query_advice(cards[0], Rotation::cur());
+            let hand_prod0 = meta.query_advice(hand_prod[0], Rotation::cur());
+            constraints.push(q_sorted.clone() * (hand_prod0 - (card0 + gamma.clone())));
+
+            for i in 1..hand_size {
+                let card_i = meta.query_advice(cards[i], Rotation::cur());
+                let prod_prev = meta.query_advice(hand_prod[i - 1], Rotation::cur());
+                let prod_i = meta.query_advice(hand_prod[i], Rotation::cur());
+                constraints.push(q_sorted.clone() * (prod_i - prod_prev * (card_i + gamma.clone())));
+            }
+
+            let pool_final = meta.query_advice(pool_prod[n_total - 1], Rotation::cur());
+            let hand_final = meta.query_advice(hand_prod[hand_size - 1], Rotation::cur());
+            constraints.push(q_sorted * (pool_final - hand_final));
 
-            constraints.push(q_four_of_a_kind * (Expression::Constant(F::from_u128(4)) - num_of_same_kind));
-        
             constraints
         });
 
-        meta.create_gate("full house", |meta| {
-            let q_pair = meta.query_selector(q_one_pair);
-            let q_three = meta.query_selector(q_three_of_a_kind);
+        meta.create_gate("hand_score packs category and tiebreak ranks", |meta| {
+            let q_sorted = meta.query_selector(q_sorted);
 
-            let num_of_same_kind = meta.query_advice(num_of_same_kind, Rotation::cur());
-            let num_of_pair = meta.query_advice(num_of_pair, Rotation::cur());
+            // is_straight_run / is_flush are is_zero-style booleans, ANDed
+            // across adjacent pairs via multiplication. Each per-pair term
+            // follows the same two-constraint is_zero gadget as `eq` above:
+            // the value definition here, plus a `diff * term == 0` check
+            // below so a prover can't forge a zero term by picking inv = 0
+            // when diff is actually nonzero.
+            let mut is_straight_run = Expression::Constant(F::one());
+            let mut is_flush = Expression::Constant(F::one());
+            let mut soundness_constraints = vec![];
+            for i in 0..hand_size - 1 {
+                let cur_rank = meta.query_advice(sorted[i], Rotation::cur());
+                let next_rank = meta.query_advice(sorted[i + 1], Rotation::cur());
+                let straight_inv_i = meta.query_advice(straight_inv[i], Rotation::cur());
+                let straight_diff = next_rank - cur_rank - Expression::Constant(F::one());
+                let straight_term = Expression::Constant(F::one()) - straight_diff.clone() * straight_inv_i;
+                soundness_constraints.push(q_sorted.clone() * (straight_diff * straight_term.clone()));
+                is_straight_run = is_straight_run * straight_term;
 
-            let mut constraints = vec![];
-            constraints.push(q_three * (Expression::Constant(F::from_u128(3)) - num_of_same_kind));
-            constraints.push(q_pair * (Expression::Constant(F::one()) - num_of_pair));
+                let cur_suit = meta.query_advice(suits[i], Rotation::cur());
+                let next_suit = meta.query_advice(suits[i + 1], Rotation::cur());
+                let flush_inv_i = meta.query_advice(flush_inv[i], Rotation::cur());
+                let flush_diff = next_suit - cur_suit;
+                let flush_term = Expression::Constant(F::one()) - flush_diff.clone() * flush_inv_i;
+                soundness_constraints.push(q_sorted.clone() * (flush_diff * flush_term.clone()));
+                is_flush = is_flush * flush_term;
+            }
+
+            // Ace-low "wheel" (A-2-3-4-5): `sorted` holds rank values, so
+            // the ace sits at 12 instead of below 2, and the run above
+            // can't see it as consecutive. `is_wheel` is a separately
+            // witnessed boolean that, when set, is forced to match the
+            // wheel's exact sorted-rank pattern; when clear it carries no
+            // constraint, so a genuine wheel hand that leaves it unset
+            // just scores as a non-straight rather than becoming unsound.
+            const WHEEL_PATTERN: [u64; 5] = [0, 1, 2, 3, 12];
+            let is_wheel = meta.query_advice(is_wheel, Rotation::cur());
+            soundness_constraints.push(q_sorted.clone() * (is_wheel.clone() * (Expression::Constant(F::one()) - is_wheel.clone())));
+            for (i, &wheel_rank) in WHEEL_PATTERN.iter().enumerate() {
+                let sorted_i = meta.query_advice(sorted[i], Rotation::cur());
+                soundness_constraints.push(
+                    q_sorted.clone() * (is_wheel.clone() * (sorted_i - Expression::Constant(F::from(wheel_rank)))),
+                );
+            }
+            let is_straight = is_straight_run.clone() + is_wheel.clone();
+
+            // Royal flush is the unique straight-flush run topping out at
+            // the ace (rank 12); the wheel can't satisfy this because
+            // `is_straight_run` (not `is_wheel`) is required here.
+            let ace_high_inv = meta.query_advice(ace_high_inv, Rotation::cur());
+            let top_rank = meta.query_advice(sorted[hand_size - 1], Rotation::cur());
+            let ace_high_diff = top_rank - Expression::Constant(F::from_u128(12));
+            let is_ace_high = Expression::Constant(F::one()) - ace_high_diff.clone() * ace_high_inv;
+            soundness_constraints.push(q_sorted.clone() * (ace_high_diff * is_ace_high.clone()));
+
+            let is_straight_flush = is_straight.clone() * is_flush.clone();
+            let is_royal_flush = is_straight_run * is_flush.clone() * is_ace_high;
+            let is_straight_flush_only = is_straight_flush.clone() - is_royal_flush.clone();
+            let is_straight_only = is_straight.clone() - is_straight_flush.clone();
+            let is_flush_only = is_flush - is_straight_flush;
+
+            let eq0 = meta.query_advice(eq[0], Rotation::cur());
+            let eq1 = meta.query_advice(eq[1], Rotation::cur());
+            let eq2 = meta.query_advice(eq[2], Rotation::cur());
+            let eq3 = meta.query_advice(eq[3], Rotation::cur());
+            let s = eq0.clone() + eq1.clone() + eq2.clone() + eq3.clone();
+            let r = eq0.clone() * eq1.clone() + eq1.clone() * eq2.clone() + eq2.clone() * eq3.clone();
+            let q = eq0 * eq1.clone() * eq2.clone() + eq1 * eq2 * eq3;
+
+            let inv2 = Expression::Constant(F::from(2u64).invert().unwrap());
+            let is_four = q.clone();
+            let is_full_house = r.clone() * (s.clone() - Expression::Constant(F::from_u128(2))) * (Expression::Constant(F::one()) - q.clone());
+            let is_three = r.clone() * (Expression::Constant(F::from_u128(3)) - s.clone()) * (Expression::Constant(F::one()) - q.clone());
+            // (1 - q) zeroes both pair terms for quads, where r == 2 would
+            // otherwise make (1 - r) == -1 instead of 0.
+            let is_two_pair = (Expression::Constant(F::one()) - r.clone()) * (Expression::Constant(F::one()) - q.clone()) * s.clone() * (s.clone() - Expression::Constant(F::one())) * inv2.clone();
+            let is_one_pair = (Expression::Constant(F::one()) - r) * (Expression::Constant(F::one()) - q) * s.clone() * (Expression::Constant(F::from_u128(2)) - s);
+
+            let category = is_one_pair.clone() * Expression::Constant(F::from_u128(W_ONE_PAIR as u128))
+                + is_two_pair.clone() * Expression::Constant(F::from_u128(W_TWO_PAIR as u128))
+                + is_three.clone() * Expression::Constant(F::from_u128(W_THREE_OF_A_KIND as u128))
+                + is_straight_only * Expression::Constant(F::from_u128(W_STRAIGHT as u128))
+                + is_flush_only * Expression::Constant(F::from_u128(W_FLUSH as u128))
+                + is_full_house.clone() * Expression::Constant(F::from_u128(W_FULL_HOUSE as u128))
+                + is_four.clone() * Expression::Constant(F::from_u128(W_FOUR_OF_A_KIND as u128))
+                + is_straight_flush_only * Expression::Constant(F::from_u128(W_STRAIGHT_FLUSH as u128))
+                + is_royal_flush * Expression::Constant(F::from_u128(W_ROYAL_FLUSH as u128));
+
+            // A flat ascending pack of `sorted` only ranks kickers
+            // correctly: it's right for straight/flush/straight-flush/
+            // royal-flush/high-card hands, where no rank repeats and the
+            // highest-weight slot already holds the top card, but wrong
+            // for one pair/two pair/trips/quads/full house, where the
+            // grouped rank(s) must outrank every kicker regardless of
+            // its numeric value. Each grouped category below packs its
+            // own rank(s) into the top weight slot(s) first, then the
+            // remaining kickers in their existing (ascending) relative
+            // order; eq0..eq3 pin down exactly which `sorted` positions
+            // are the grouped ones, so no case-by-case branching on
+            // concrete rank values is needed.
+            let sorted0 = meta.query_advice(sorted[0], Rotation::cur());
+            let sorted1 = meta.query_advice(sorted[1], Rotation::cur());
+            let sorted2 = meta.query_advice(sorted[2], Rotation::cur());
+            let sorted3 = meta.query_advice(sorted[3], Rotation::cur());
+            let sorted4 = meta.query_advice(sorted[4], Rotation::cur());
+            let tb_eq0 = meta.query_advice(eq[0], Rotation::cur());
+            let tb_eq1 = meta.query_advice(eq[1], Rotation::cur());
+            let tb_eq2 = meta.query_advice(eq[2], Rotation::cur());
+            let tb_eq3 = meta.query_advice(eq[3], Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            let rank_base = Expression::Constant(F::from(1u64 << TIEBREAK_BITS_PER_RANK));
+            let rank_base2 = rank_base.clone() * rank_base.clone();
+            let rank_base3 = rank_base2.clone() * rank_base.clone();
+            let rank_base4 = rank_base3.clone() * rank_base.clone();
+
+            // The wheel (A-2-3-4-5) is poker's weakest straight, but
+            // `sorted` holds the ace at its raw rank 12 rather than
+            // below the 2, so packing it in its usual top slot would
+            // rank the wheel above every other straight instead of
+            // below all of them. When `is_wheel` is set, drop the ace
+            // (forced to rank 12 by the soundness constraints above)
+            // entirely and shift 2..5 up one slot, leaving the bottom
+            // slot at zero — below even a 6-high straight's tiebreak.
+            let ascending_tiebreak = sorted0.clone()
+                + sorted1.clone() * rank_base.clone()
+                + sorted2.clone() * rank_base2.clone()
+                + sorted3.clone() * rank_base3.clone()
+                + sorted4.clone() * rank_base4.clone();
+            let wheel_tiebreak = sorted0.clone() * rank_base.clone()
+                + sorted1.clone() * rank_base2.clone()
+                + sorted2.clone() * rank_base3.clone()
+                + sorted3.clone() * rank_base4.clone();
+            let plain_tiebreak =
+                is_wheel.clone() * wheel_tiebreak + (one.clone() - is_wheel) * ascending_tiebreak;
+
+            // One pair: eq0..eq3 select which adjacent pair in `sorted`
+            // is the pair; the other three entries are the kickers,
+            // already ascending.
+            let one_pair_rank = tb_eq0.clone() * sorted0.clone()
+                + tb_eq1.clone() * sorted1.clone()
+                + tb_eq2.clone() * sorted2.clone()
+                + tb_eq3.clone() * sorted3.clone();
+            let one_pair_kickers = tb_eq0.clone()
+                * (sorted2.clone() + sorted3.clone() * rank_base.clone() + sorted4.clone() * rank_base2.clone())
+                + tb_eq1.clone()
+                    * (sorted0.clone() + sorted3.clone() * rank_base.clone() + sorted4.clone() * rank_base2.clone())
+                + tb_eq2.clone()
+                    * (sorted0.clone() + sorted1.clone() * rank_base.clone() + sorted4.clone() * rank_base2.clone())
+                + tb_eq3.clone()
+                    * (sorted0.clone() + sorted1.clone() * rank_base.clone() + sorted2.clone() * rank_base2.clone());
+            let one_pair_tiebreak = one_pair_rank * rank_base4.clone() + one_pair_kickers;
 
+            // Two pair: the two pairs sit at (eq0,eq2), (eq0,eq3) or
+            // (eq1,eq3); summing every sorted rank and subtracting each
+            // paired rank twice leaves exactly the kicker.
+            let two_pair_high = tb_eq3.clone() * sorted3.clone()
+                + (one.clone() - tb_eq3.clone()) * sorted2.clone();
+            let two_pair_low = tb_eq0.clone() * sorted0.clone()
+                + (one.clone() - tb_eq0.clone()) * sorted1.clone();
+            let two_pair_sum = sorted0.clone() + sorted1.clone() + sorted2.clone() + sorted3.clone() + sorted4.clone();
+            let two_pair_kicker = two_pair_sum
+                - (two_pair_high.clone() + two_pair_low.clone()) * Expression::Constant(F::from_u128(2));
+            let two_pair_tiebreak = two_pair_high * rank_base4.clone()
+                + two_pair_low * rank_base3.clone()
+                + two_pair_kicker * rank_base2.clone();
+
+            // Three of a kind: the triple is (0,1,2), (1,2,3) or
+            // (2,3,4); eq0 alone flags the first, eq3 alone the last.
+            let three_rank = tb_eq0.clone() * sorted1.clone()
+                + tb_eq3.clone() * sorted3.clone()
+                + (one.clone() - tb_eq0.clone() - tb_eq3.clone()) * sorted2.clone();
+            let three_kicker_high =
+                tb_eq3.clone() * sorted1.clone() + (one.clone() - tb_eq3.clone()) * sorted4.clone();
+            let three_kicker_low =
+                tb_eq0.clone() * sorted3.clone() + (one.clone() - tb_eq0.clone()) * sorted0.clone();
+            let three_tiebreak = three_rank * rank_base4.clone()
+                + three_kicker_high * rank_base3.clone()
+                + three_kicker_low * rank_base2.clone();
+
+            // Four of a kind: the quad is (0,1,2,3) or (1,2,3,4); eq0
+            // alone flags the first (a fifth copy of the same rank is
+            // impossible once pool cards are pairwise distinct).
+            let four_rank =
+                tb_eq0.clone() * sorted1.clone() + (one.clone() - tb_eq0.clone()) * sorted2.clone();
+            let four_kicker =
+                tb_eq0.clone() * sorted4.clone() + (one.clone() - tb_eq0.clone()) * sorted0.clone();
+            let four_tiebreak = four_rank * rank_base4.clone() + four_kicker * rank_base3.clone();
+
+            // Full house: the trips always outrank the pair, whichever
+            // rank is numerically higher. eq1 alone tells apart "trips
+            // low (0,1,2), pair high (3,4)" from "pair low (0,1), trips
+            // high (2,3,4)".
+            let full_house_trips =
+                tb_eq1.clone() * sorted1.clone() + (one.clone() - tb_eq1.clone()) * sorted2.clone();
+            let full_house_pair =
+                tb_eq1.clone() * sorted3.clone() + (one.clone() - tb_eq1.clone()) * sorted0.clone();
+            let full_house_tiebreak =
+                full_house_trips * rank_base4.clone() + full_house_pair * rank_base3.clone();
+
+            let grouped_category =
+                is_one_pair.clone() + is_two_pair.clone() + is_three.clone() + is_four.clone() + is_full_house.clone();
+            let tiebreak = is_one_pair * one_pair_tiebreak
+                + is_two_pair * two_pair_tiebreak
+                + is_three * three_tiebreak
+                + is_four * four_tiebreak
+                + is_full_house * full_house_tiebreak
+                + (one - grouped_category) * plain_tiebreak;
+
+            let category_shift = F::from(1u64 << (TIEBREAK_BITS_PER_RANK as u64 * hand_size as u64));
+            let hand_score = meta.query_advice(hand_score, Rotation::cur());
+
+            let mut constraints = soundness_constraints;
+            constraints.push(q_sorted * (hand_score - (category * Expression::Constant(category_shift) + tiebreak)));
             constraints
         });
 
-        VanillaHoldemConfig { 
-            q_straight,
-            q_flush,
-            q_one_pair,
-            q_two_pair,
-            q_three_of_a_kind,
-            q_four_of_a_kind,
+        VanillaHoldemConfig {
+            params,
+            q_sorted,
             cards,
+            ranks,
+            suits,
+            sorted,
+            eq,
+            eq_inv,
+            straight_inv,
+            flush_inv,
+            is_wheel,
+            ace_high_inv,
+            perm_prod,
+            pool_cards,
+            sorted_pool,
+            pool_perm_prod,
+            selected,
+            pool_prod,
+            hand_prod,
+            hand_score,
+            gamma,
             table_cards,
+            hand_score_instance,
+            card_table,
+            range_table,
+            card_gap_table,
         }
     }
 
-    pub fn assign_card(
+    /// Splits a raw card value (0..52) into its `(rank, suit)` witnesses.
+    /// The split itself is untrusted until the per-card lookup argument
+    /// ties `card`, `rank` and `suit` back to a row of `card_table`.
+    fn card_to_rank_suit(card: Value<Assigned<F>>) -> (Value<F>, Value<F>) {
+        card.map(|c| {
+            let index = c.evaluate().get_lower_32();
+            (F::from((index % 13) as u64), F::from((index / 13) as u64))
+        })
+        .unzip()
+    }
+
+    /// Sorts witnessed field values in ascending order. Untrusted on its
+    /// own: callers must tie the result back to the input via a
+    /// grand-product permutation argument (`ranks`/`sorted` and
+    /// `pool_cards`/`sorted_pool` both follow this pattern).
+    fn sort_values(values: &[Value<F>]) -> Vec<Value<F>> {
+        let zipped: Value<Vec<F>> = values.iter().fold(Value::known(Vec::with_capacity(values.len())), |acc, r| {
+            acc.zip(*r).map(|(mut v, x)| {
+                v.push(x);
+                v
+            })
+        });
+        let sorted: Value<Vec<F>> = zipped.map(|mut v| {
+            v.sort_by_key(|f| f.get_lower_32());
+            v
+        });
+
+        (0..values.len()).map(|i| sorted.clone().map(|v| v[i])).collect()
+    }
+
+    /// Scores a 5-card hand (raw card indices 0..52) the same way the
+    /// `hand_score` gate does: category in the high bits, descending
+    /// ranks packed 4 bits apiece below it.
+    fn evaluate_five(cards: [u32; 5]) -> u64 {
+        let ranks: [u32; 5] = cards.map(|c| c % 13);
+        let suits: [u32; 5] = cards.map(|c| c / 13);
+
+        let mut sorted = ranks;
+        sorted.sort_unstable();
+
+        let is_flush = suits.iter().all(|&s| s == suits[0]);
+        let is_wheel = sorted == [0, 1, 2, 3, 12];
+        let is_straight_run = (1..5).all(|i| sorted[i] == sorted[i - 1] + 1);
+        let is_straight = is_straight_run || is_wheel;
+        let is_royal_flush = is_straight_run && is_flush && sorted[4] == 12;
+        let is_straight_flush = is_straight && is_flush;
+
+        let eq: Vec<u64> = (0..4).map(|i| (sorted[i + 1] == sorted[i]) as u64).collect();
+        let s: i64 = eq.iter().sum::<u64>() as i64;
+        let r: i64 = (eq[0] * eq[1] + eq[1] * eq[2] + eq[2] * eq[3]) as i64;
+        let q: i64 = (eq[0] * eq[1] * eq[2] + eq[1] * eq[2] * eq[3]) as i64;
+        let num_of_pair = (s - 2 * r + q).max(0) as u64;
+        let num_of_same_kind = (1 + 2 * r - q).max(1) as u64;
+
+        let category = if is_royal_flush {
+            W_ROYAL_FLUSH
+        } else if is_straight_flush {
+            W_STRAIGHT_FLUSH
+        } else if num_of_same_kind == 4 {
+            W_FOUR_OF_A_KIND
+        } else if num_of_same_kind == 3 && num_of_pair == 1 {
+            W_FULL_HOUSE
+        } else if is_flush {
+            W_FLUSH
+        } else if is_straight {
+            W_STRAIGHT
+        } else if num_of_same_kind == 3 {
+            W_THREE_OF_A_KIND
+        } else if num_of_pair == 2 {
+            W_TWO_PAIR
+        } else if num_of_pair == 1 {
+            W_ONE_PAIR
+        } else {
+            0
+        };
+
+        // A flat ascending pack of `sorted` is only correct when no rank
+        // repeats (straight/flush/straight-flush/royal-flush/high card):
+        // then the highest-weight slot already holds the top card. For
+        // one pair/two pair/trips/quads/full house the grouped rank(s)
+        // must outrank every kicker regardless of its numeric value, so
+        // each is packed rank(s)-first into the top slot(s), kickers
+        // into the slots below — the same slot assignment per category
+        // as the `hand_score` gate (see its comments for why each
+        // eq0..eq3 case split is exhaustive), so the two always agree
+        // bit-for-bit.
+        let slot = |rank: u32, i: u32| (rank as u64) << (TIEBREAK_BITS_PER_RANK * i);
+        let [eq0, eq1, eq2, eq3] = [eq[0], eq[1], eq[2], eq[3]];
+        let tiebreak = if num_of_same_kind == 4 {
+            // Four of a kind: the quad is (0,1,2,3) or (1,2,3,4).
+            let (rank, kicker) = if eq0 == 1 {
+                (sorted[1], sorted[4])
+            } else {
+                (sorted[2], sorted[0])
+            };
+            slot(rank, 4) + slot(kicker, 3)
+        } else if num_of_same_kind == 3 && num_of_pair == 1 {
+            // Full house: trips always outrank the pair.
+            let (trips, pair) = if eq1 == 1 {
+                (sorted[1], sorted[3])
+            } else {
+                (sorted[2], sorted[0])
+            };
+            slot(trips, 4) + slot(pair, 3)
+        } else if num_of_same_kind == 3 {
+            // Three of a kind: the triple is (0,1,2), (1,2,3) or (2,3,4).
+            let (rank, kicker_low, kicker_high) = if eq0 == 1 {
+                (sorted[1], sorted[3], sorted[4])
+            } else if eq3 == 1 {
+                (sorted[3], sorted[0], sorted[1])
+            } else {
+                (sorted[2], sorted[0], sorted[4])
+            };
+            slot(rank, 4) + slot(kicker_high, 3) + slot(kicker_low, 2)
+        } else if num_of_pair == 2 {
+            // Two pair: the two pairs are (0,2), (0,3) or (1,3); the
+            // kicker is whichever of the five ranks isn't in a pair.
+            let (low, high) = if eq0 == 1 && eq2 == 1 {
+                (sorted[0], sorted[2])
+            } else if eq0 == 1 && eq3 == 1 {
+                (sorted[0], sorted[3])
+            } else {
+                (sorted[1], sorted[3])
+            };
+            let kicker = sorted.iter().copied().sum::<u32>() - 2 * low - 2 * high;
+            slot(high, 4) + slot(low, 3) + slot(kicker, 2)
+        } else if num_of_pair == 1 {
+            // One pair: eq0..eq3 flag which adjacent slots hold the pair.
+            let (rank, kickers) = if eq0 == 1 {
+                (sorted[0], [sorted[2], sorted[3], sorted[4]])
+            } else if eq1 == 1 {
+                (sorted[1], [sorted[0], sorted[3], sorted[4]])
+            } else if eq2 == 1 {
+                (sorted[2], [sorted[0], sorted[1], sorted[4]])
+            } else {
+                (sorted[3], [sorted[0], sorted[1], sorted[2]])
+            };
+            slot(rank, 4) + slot(kickers[0], 0) + slot(kickers[1], 1) + slot(kickers[2], 2)
+        } else if is_wheel {
+            // The wheel (A-2-3-4-5) is the weakest straight, but `sorted`
+            // holds the ace at its raw rank 12. Drop the ace and shift
+            // 2..5 up one slot so the wheel packs below a 6-high
+            // straight's tiebreak instead of above every other straight,
+            // mirroring the `hand_score` gate's wheel override.
+            slot(sorted[0], 1) + slot(sorted[1], 2) + slot(sorted[2], 3) + slot(sorted[3], 4)
+        } else {
+            (0..5).map(|i| slot(sorted[i], i as u32)).sum()
+        };
+
+        (category << (TIEBREAK_BITS_PER_RANK * 5)) + tiebreak
+    }
+
+    /// Picks the 5-card subset of `pool` with the highest `evaluate_five`
+    /// score, respecting whichever selection rule `n_hole` implies:
+    /// `pool.len() == hand_size` is the trivial "use everything" case;
+    /// `n_hole == 2` brute-forces all 21 5-of-7 subsets (Texas Hold'em,
+    /// no constraint on how many hole cards are used); `n_hole == 4`
+    /// brute-forces the 60 (2-of-4 hole) x (3-of-board) combinations
+    /// (Omaha's "must use exactly 2 hole cards" rule).
+    fn select_best_hand(hand_size: usize, n_hole: usize, pool: &[u32]) -> (Vec<bool>, Vec<u32>, u64) {
+        if pool.len() == hand_size {
+            let score = if hand_size == 5 {
+                Self::evaluate_five(pool.try_into().unwrap())
+            } else {
+                0
+            };
+            return (vec![true; pool.len()], pool.to_vec(), score);
+        }
+
+        assert_eq!(hand_size, 5, "only 5-card hands are supported for pool.len() != hand_size");
+
+        let n = pool.len();
+        let mut best: Option<(u64, Vec<bool>, Vec<u32>)> = None;
+        let mut consider = |selected: Vec<bool>, hand: Vec<u32>| {
+            let score = Self::evaluate_five(hand.clone().try_into().unwrap());
+            if best.as_ref().map_or(true, |(best_score, _, _)| score > *best_score) {
+                best = Some((score, selected, hand));
+            }
+        };
+
+        if n_hole == 4 {
+            // Omaha: exactly 2 of the 4 hole cards, exactly 3 of the board.
+            let n_board = n - n_hole;
+            for hole_mask in 0u32..(1 << n_hole) {
+                if hole_mask.count_ones() != 2 {
+                    continue;
+                }
+                for board_mask in 0u32..(1 << n_board) {
+                    if board_mask.count_ones() != 3 {
+                        continue;
+                    }
+                    let selected: Vec<bool> = (0..n)
+                        .map(|i| {
+                            if i < n_hole {
+                                (hole_mask >> i) & 1 == 1
+                            } else {
+                                (board_mask >> (i - n_hole)) & 1 == 1
+                            }
+                        })
+                        .collect();
+                    let hand: Vec<u32> = (0..n).filter(|&i| selected[i]).map(|i| pool[i]).collect();
+                    consider(selected, hand);
+                }
+            }
+        } else {
+            assert_eq!(
+                (n, n_hole),
+                (7, 2),
+                "only Texas Hold'em's best-5-of-7 and Omaha's 2-hole/3-board selection are supported",
+            );
+            for mask in 0u32..(1 << n) {
+                if mask.count_ones() as usize != 5 {
+                    continue;
+                }
+                let selected: Vec<bool> = (0..n).map(|i| (mask >> i) & 1 == 1).collect();
+                let hand: Vec<u32> = (0..n).filter(|&i| selected[i]).map(|i| pool[i]).collect();
+                consider(selected, hand);
+            }
+        }
+
+        let (score, selected, hand) = best.expect("at least one valid 5-card hand exists in the pool");
+        (selected, hand, score)
+    }
+
+    pub fn load_tables(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        self.config.card_table.load(layouter)?;
+        self.config.range_table.load(layouter)?;
+        self.config.card_gap_table.load(layouter)?;
+        Ok(())
+    }
+
+    /// Derives, off-circuit, which pool cards make up the best `hand_size`
+    /// subset, together with the boolean selection flags and the packed
+    /// `hand_score`. Structured as a single `Value::map` so it becomes a
+    /// no-op (propagating `Value::unknown()`) during key generation.
+    #[allow(clippy::type_complexity)]
+    fn select_hand(
+        &self,
+        pool: &[Value<Assigned<F>>],
+    ) -> (Vec<Value<F>>, Vec<Value<Assigned<F>>>, Value<F>, Value<u64>) {
+        let hand_size = self.config.params.hand_size;
+        let n = pool.len();
+
+        let indices: Value<Vec<u32>> = pool.iter().fold(Value::known(Vec::with_capacity(n)), |acc, c| {
+            acc.zip(*c).map(|(mut v, x)| {
+                v.push(x.evaluate().get_lower_32());
+                v
+            })
+        });
+
+        let n_hole = self.config.params.n_hole;
+        let selection: Value<(Vec<bool>, Vec<u32>, u64)> =
+            indices.map(|pool| Self::select_best_hand(hand_size, n_hole, &pool));
+
+        let selected = (0..n)
+            .map(|i| selection.clone().map(|(sel, _, _)| if sel[i] { F::one() } else { F::zero() }))
+            .collect();
+        let hand = (0..hand_size)
+            .map(|j| selection.clone().map(|(_, hand, _)| Assigned::from(F::from(hand[j] as u64))))
+            .collect();
+        let hand_score_raw = selection.clone().map(|(_, _, score)| score);
+        let hand_score = selection.map(|(_, _, score)| F::from(score));
+
+        (selected, hand, hand_score, hand_score_raw)
+    }
+
+    /// Assigns everything that doesn't depend on the `gamma` challenge:
+    /// the card pool and its selection flags, the sorted pool (for the
+    /// pool-distinctness argument), the chosen hand and its rank/suit
+    /// decomposition, the sorted ranks, the adjacency (`eq`) witnesses,
+    /// and `hand_score`. Returns the pool/hand/rank/sorted values so the
+    /// second-phase grand products can be assigned without re-deriving
+    /// them from scratch, plus the assigned `hand_score` cell (so callers
+    /// like `ShowdownChip` can copy it elsewhere) and its plain `u64`
+    /// value (for off-circuit comparison).
+    #[allow(clippy::type_complexity)]
+    fn assign_first_phase(
         &self,
         mut layouter: impl Layouter<F>,
-        cards: [Value<Assigned<F>>; 2],
-        table_cards: [Value<Assigned<F>>; 3],
-    ) -> Result<(), Error> {
-        let hand: Vec<Value<Assigned<F>>> = cards.iter().chain(table_cards.iter()).map(|v| *v).collect();
-        let hand: [Value<Assigned<F>>; 5] = hand.try_into().unwrap();
+        pool: &[Value<Assigned<F>>],
+    ) -> Result<
+        (
+            Vec<Value<F>>,
+            Vec<Value<Assigned<F>>>,
+            Vec<Value<F>>,
+            Vec<Value<F>>,
+            Vec<Value<F>>,
+            AssignedCell<F, F>,
+            Value<u64>,
+        ),
+        Error,
+    > {
+        let hand_size = self.config.params.hand_size;
+        let (selected, hand, hand_score, hand_score_raw) = self.select_hand(pool);
 
         layouter.assign_region(
-            || "hand check",
+            || "hand check (first phase)",
             |mut region| {
-                for i in 0..5 {
+                self.config.q_sorted.enable(&mut region, 0)?;
+
+                let n_hole = self.config.params.n_hole;
+                let mut pool_vals = vec![Value::unknown(); pool.len()];
+                for i in 0..pool.len() {
+                    let pool_cell =
+                        region.assign_advice(|| "pool card", self.config.pool_cards[i], 0, || pool[i])?;
+                    region.assign_advice(|| "selected", self.config.selected[i], 0, || selected[i])?;
+                    pool_vals[i] = pool[i].map(|v| v.evaluate());
+
+                    // Board positions must equal the public board, not just
+                    // be *some* valid card the prover chose off-chain --
+                    // otherwise a prover could compute hand_score against a
+                    // self-chosen board while publishing a different one.
+                    if i >= n_hole {
+                        region.constrain_instance(
+                            pool_cell.cell(),
+                            self.config.table_cards[i - n_hole],
+                            0,
+                        )?;
+                    }
+                }
+
+                let sorted_pool = Self::sort_values(&pool_vals);
+                for i in 0..pool.len() {
+                    region.assign_advice(|| "sorted pool card", self.config.sorted_pool[i], 0, || sorted_pool[i])?;
+                }
+
+                let mut ranks = vec![Value::unknown(); hand_size];
+                for i in 0..hand_size {
                     region.assign_advice(|| "cards", self.config.cards[i], 0, || hand[i])?;
+
+                    let (rank, suit) = Self::card_to_rank_suit(hand[i]);
+                    region.assign_advice(|| "rank", self.config.ranks[i], 0, || rank)?;
+                    region.assign_advice(|| "suit", self.config.suits[i], 0, || suit)?;
+                    ranks[i] = rank;
+                }
+
+                let sorted = Self::sort_values(&ranks);
+                for i in 0..hand_size {
+                    region.assign_advice(|| "sorted rank", self.config.sorted[i], 0, || sorted[i])?;
+                }
+
+                let mut eqs = vec![Value::known(F::zero()); hand_size - 1];
+                for i in 0..hand_size - 1 {
+                    let diff = sorted[i + 1] - sorted[i];
+                    let diff_inv = diff.map(|d| d.invert().unwrap_or(F::zero()));
+                    let eq_i = Value::known(F::one()) - diff * diff_inv;
+
+                    region.assign_advice(|| "eq", self.config.eq[i], 0, || eq_i)?;
+                    region.assign_advice(|| "eq_inv", self.config.eq_inv[i], 0, || diff_inv)?;
+                    eqs[i] = eq_i;
+
+                    let straight_diff = diff - Value::known(F::one());
+                    let straight_inv = straight_diff.map(|d| d.invert().unwrap_or(F::zero()));
+                    region.assign_advice(|| "straight_inv", self.config.straight_inv[i], 0, || straight_inv)?;
+                }
+
+                for i in 0..hand_size - 1 {
+                    let suit_i = Self::card_to_rank_suit(hand[i]).1;
+                    let suit_next = Self::card_to_rank_suit(hand[i + 1]).1;
+                    let flush_diff = suit_next - suit_i;
+                    let flush_inv = flush_diff.map(|d| d.invert().unwrap_or(F::zero()));
+                    region.assign_advice(|| "flush_inv", self.config.flush_inv[i], 0, || flush_inv)?;
+                }
+
+                const WHEEL_PATTERN: [u64; 5] = [0, 1, 2, 3, 12];
+                let mut is_wheel = Value::known(true);
+                for i in 0..hand_size {
+                    let matches_wheel = sorted[i].map(|v| v == F::from(WHEEL_PATTERN[i]));
+                    is_wheel = is_wheel.zip(matches_wheel).map(|(a, b)| a && b);
+                }
+                let is_wheel = is_wheel.map(|b| if b { F::one() } else { F::zero() });
+                region.assign_advice(|| "is_wheel", self.config.is_wheel, 0, || is_wheel)?;
+
+                let ace_high_diff = sorted[hand_size - 1] - Value::known(F::from_u128(12));
+                let ace_high_inv = ace_high_diff.map(|d| d.invert().unwrap_or(F::zero()));
+                region.assign_advice(|| "ace_high_inv", self.config.ace_high_inv, 0, || ace_high_inv)?;
+
+                let hand_score_cell =
+                    region.assign_advice(|| "hand_score", self.config.hand_score, 0, || hand_score)?;
+                region.constrain_instance(hand_score_cell.cell(), self.config.hand_score_instance, 0)?;
+
+                Ok((ranks.clone(), hand.clone(), selected.clone(), sorted, sorted_pool, hand_score_cell, hand_score_raw))
+            },
+        )
+    }
+
+    /// Assigns the second-phase grand-product columns: the rank
+    /// permutation argument (`sorted` is a permutation of `ranks`), the
+    /// pool-distinctness argument (`sorted_pool` is a permutation of
+    /// `pool_cards`), and the selection argument (the chosen hand is a
+    /// selected sub-multiset of the pool), once `gamma` is available.
+    #[allow(clippy::too_many_arguments)]
+    fn assign_second_phase(
+        &self,
+        mut layouter: impl Layouter<F>,
+        pool: &[Value<Assigned<F>>],
+        selected: &[Value<F>],
+        hand: &[Value<Assigned<F>>],
+        ranks: &[Value<F>],
+        sorted: &[Value<F>],
+        sorted_pool: &[Value<F>],
+        gamma: Value<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "hand check (second phase)",
+            |mut region| {
+                let mut prod = Value::known(F::one());
+                for i in 0..ranks.len() {
+                    prod = prod * (ranks[i] + gamma) * (sorted[i] + gamma).map(|v| v.invert().unwrap());
+                    region.assign_advice(|| "perm_prod", self.config.perm_prod[i], 0, || prod)?;
+                }
+
+                let mut pool_perm_prod = Value::known(F::one());
+                for i in 0..pool.len() {
+                    let pool_val = pool[i].map(|v| v.evaluate());
+                    pool_perm_prod = pool_perm_prod
+                        * (pool_val + gamma)
+                        * (sorted_pool[i] + gamma).map(|v| v.invert().unwrap());
+                    region.assign_advice(|| "pool_perm_prod", self.config.pool_perm_prod[i], 0, || pool_perm_prod)?;
+                }
+
+                let one = Value::known(F::one());
+                let mut pool_prod = Value::known(F::one());
+                for i in 0..pool.len() {
+                    let pool_val = pool[i].map(|v| v.evaluate());
+                    let term = selected[i] * (pool_val + gamma) + (one - selected[i]);
+                    pool_prod = pool_prod * term;
+                    region.assign_advice(|| "pool_prod", self.config.pool_prod[i], 0, || pool_prod)?;
                 }
-                
+
+                let mut hand_prod = Value::known(F::one());
+                for i in 0..hand.len() {
+                    let hand_val = hand[i].map(|v| v.evaluate());
+                    hand_prod = hand_prod * (hand_val + gamma);
+                    region.assign_advice(|| "hand_prod", self.config.hand_prod[i], 0, || hand_prod)?;
+                }
+
                 Ok(())
-            }
+            },
         )
     }
+
+    pub fn assign_card(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[Value<Assigned<F>>],
+        table_cards: &[Value<Assigned<F>>],
+    ) -> Result<(), Error> {
+        self.assign_card_scored(layouter.namespace(|| "hand"), cards, table_cards)?;
+        Ok(())
+    }
+
+    /// Same as `assign_card`, but also returns the assigned `hand_score`
+    /// cell (so e.g. `ShowdownChip` can copy it into a comparison region)
+    /// together with its plain `u64` value for off-circuit comparison.
+    pub fn assign_card_scored(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[Value<Assigned<F>>],
+        table_cards: &[Value<Assigned<F>>],
+    ) -> Result<(AssignedCell<F, F>, Value<u64>), Error> {
+        let params = self.config.params;
+        assert_eq!(cards.len(), params.n_hole, "hole card count does not match HoldemParams");
+        assert_eq!(table_cards.len(), params.n_board, "board card count does not match HoldemParams");
+
+        let pool: Vec<Value<Assigned<F>>> = cards.iter().chain(table_cards.iter()).copied().collect();
+
+        let (ranks, hand, selected, sorted, sorted_pool, hand_score_cell, hand_score_raw) =
+            self.assign_first_phase(layouter.namespace(|| "first phase"), &pool)?;
+        let gamma = layouter.get_challenge(self.config.gamma);
+        self.assign_second_phase(
+            layouter.namespace(|| "second phase"),
+            &pool,
+            &selected,
+            &hand,
+            &ranks,
+            &sorted,
+            &sorted_pool,
+            gamma,
+        )?;
+
+        Ok((hand_score_cell, hand_score_raw))
+    }
 }
 
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct VanillaHoldemCircuit<F: FieldExt> {
-    pub cards: [Value<Assigned<F>>; 2],
-    pub table_cards: [Value<Assigned<F>>; 3],
+    pub params: HoldemParams,
+    pub cards: Vec<Value<Assigned<F>>>,
+    pub table_cards: Vec<Value<Assigned<F>>>,
+}
+
+impl<F: FieldExt> Default for VanillaHoldemCircuit<F> {
+    fn default() -> Self {
+        let params = HoldemParams::default();
+        Self {
+            params,
+            cards: vec![Value::unknown(); params.n_hole],
+            table_cards: vec![Value::unknown(); params.n_board],
+        }
+    }
 }
 
 impl<F: FieldExt> Circuit<F> for VanillaHoldemCircuit<F> {
     type Config = VanillaHoldemConfig;
     type FloorPlanner = V1;
+    type Params = HoldemParams;
 
     fn without_witnesses(&self) -> Self {
-        Self::default()
+        Self {
+            params: self.params,
+            cards: vec![Value::unknown(); self.params.n_hole],
+            table_cards: vec![Value::unknown(); self.params.n_board],
+        }
+    }
+
+    fn params(&self) -> Self::Params {
+        self.params
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let q_flush = meta.selector();
-        let q_straight = meta.selector();
-        let q_one_pair = meta.selector();
-        let q_two_pair = meta.selector();
-        let q_three_of_a_kind = meta.selector();
-        let q_four_of_a_kind = meta.selector();
-
-        let cards = [
-            meta.advice_column(),
-            meta.advice_column(),
-            meta.advice_column(),
-            meta.advice_column(),
-            meta.advice_column(),
-        ];
-        let table_cards = [
-            meta.instance_column(),
-            meta.instance_column(),
-        ];
-        let num_of_pair = meta.advice_column();
-        let num_of_same_kind = meta.advice_column();
-
-        VanillaHoldemChip::configure(meta,
-            q_flush,
-            q_straight,
-            q_one_pair,
-            q_two_pair,
-            q_three_of_a_kind,
-            q_four_of_a_kind,
-            cards,
-            table_cards,
-            num_of_pair,
-            num_of_same_kind,
-        )
+        Self::configure_with_params(meta, HoldemParams::default())
+    }
+
+    fn configure_with_params(meta: &mut ConstraintSystem<F>, params: Self::Params) -> Self::Config {
+        let card_table = CardTableConfig::configure(meta);
+        let range_table = RangeTableConfig::configure(meta);
+        let card_gap_table = CardGapTableConfig::configure(meta);
+        VanillaHoldemChip::configure_columns(meta, params, card_table, range_table, card_gap_table)
     }
 
     fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<F>,) -> Result<(), Error> {
         let chip = VanillaHoldemChip::construct(config);
 
+        chip.load_tables(&mut layouter)?;
+
         chip.assign_card(
             layouter.namespace(|| "one hand"),
-            self.cards,
-            self.table_cards,
+            &self.cards,
+            &self.table_cards,
         )?;
 
         Ok(())
     }
 }
+
+/// Name of the gate that actually derives the hand category and packs it
+/// (together with the tiebreak ranks) into `hand_score`. Earlier this
+/// report walked nine standalone per-category gates (`straight`,
+/// `flush`, `one pair`, ...), but those never had their selector
+/// enabled anywhere and have since been removed as dead weight -- this
+/// fused gate was always the only place category derivation actually
+/// happened.
+const HAND_SCORE_GATE: &str = "hand_score packs category and tiebreak ranks";
+
+/// Constraint count and distinct advice columns of a single gate in the
+/// configured `ConstraintSystem`. `rows` is the number of polynomial
+/// constraints the gate contributes (`Gate::polynomials().len()`), not a
+/// count of circuit rows. Lookups aren't broken out per-gate here:
+/// a `Lookup::name()` is the name given to the `meta.lookup(...)` call
+/// site, never the name of a `create_gate` it happens to share columns
+/// with, so matching lookups against `HAND_SCORE_GATE` by name can only
+/// ever find zero -- see `HoldemCostReport::total_lookups` for the
+/// circuit-wide count instead.
+#[derive(Debug, Clone)]
+pub struct HandGateCost {
+    pub name: &'static str,
+    pub rows: usize,
+    pub advice_columns: usize,
+}
+
+/// Proof-size / minimum-`k` report for a `VanillaHoldemCircuit`, combining
+/// halo2's built-in `CircuitCost` model with a breakdown of the gate that
+/// derives hand category and tiebreak ranks out of the `ConstraintSystem`
+/// it configures.
+#[derive(Debug, Clone)]
+pub struct HoldemCostReport {
+    pub k: u32,
+    /// `CircuitCost::marginal_proof_size`, i.e. the per-instance-column
+    /// contribution to proof size, as halo2 estimates it at `k`.
+    pub marginal_proof_size: String,
+    /// `CircuitCost::proof_size` for this circuit's own instance column
+    /// layout (one per board card, plus the packed `hand_score`).
+    pub proof_size: String,
+    pub hand_score_gate: HandGateCost,
+    /// Total number of lookup arguments registered on the
+    /// `ConstraintSystem` (the `card_table`/`range_table`/`card_gap_table`
+    /// lookups). Not attributed to `hand_score_gate` specifically: see
+    /// `HandGateCost`'s doc comment.
+    pub total_lookups: usize,
+}
+
+impl<F: FieldExt> VanillaHoldemCircuit<F> {
+    /// Re-runs `configure_with_params` in a scratch `ConstraintSystem` and
+    /// reports how many constraints/columns the `hand_score` gate has and
+    /// how many lookups the circuit registers overall, alongside halo2's
+    /// `CircuitCost` estimate of proof size at `k`.
+    ///
+    /// Meant for maintainers: every time a subsystem is added (a lookup
+    /// table, the sorted-rank permutation, a second hand for showdown...)
+    /// this shows its effect on proof size and the minimum viable `k`
+    /// without needing a full keygen run.
+    pub fn cost_report<G: PrimeGroup<Scalar = F>>(&self, k: u32) -> HoldemCostReport {
+        let mut meta = ConstraintSystem::<F>::default();
+        Self::configure_with_params(&mut meta, self.params);
+
+        let matching = meta.gates().iter().filter(|gate| gate.name() == HAND_SCORE_GATE);
+        let rows = matching.clone().map(|gate| gate.polynomials().len()).sum();
+        let advice_columns = matching
+            .flat_map(|gate| gate.queried_cells().iter().map(|cell| cell.column.index()))
+            .collect::<BTreeSet<_>>()
+            .len();
+        let hand_score_gate = HandGateCost { name: HAND_SCORE_GATE, rows, advice_columns };
+        let total_lookups = meta.lookups().len();
+
+        let num_instance_columns = self.params.n_board + 1;
+        let cost = CircuitCost::<G, Self>::measure(k, self);
+
+        HoldemCostReport {
+            k,
+            marginal_proof_size: format!("{:?}", cost.marginal_proof_size()),
+            proof_size: format!("{:?}", cost.proof_size(num_instance_columns)),
+            total_lookups,
+            hand_score_gate,
+        }
+    }
+}
+
+/// Number of bits per limb used to range-check the showdown score
+/// difference. Two limbs of this width cover scores up to `2^24`, above
+/// the `(W_ROYAL_FLUSH + 1) << 20` ceiling `hand_score` stays below.
+const SCORE_LIMB_BITS: u32 = 12;
+
+/// Fixed range table over `0..2^SCORE_LIMB_BITS`, used to bound each limb
+/// of the decomposed `score_a - score_b` difference so a prover can't
+/// pick the wrong sign and still pass the check (see `ShowdownChip`).
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreLimbTableConfig {
+    pub limb: TableColumn,
+}
+
+impl ScoreLimbTableConfig {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self { limb: meta.lookup_table_column() }
+    }
+
+    pub fn load<F: FieldExt>(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "score limb range table",
+            |mut table| {
+                for i in 0..(1usize << SCORE_LIMB_BITS) {
+                    table.assign_cell(|| "limb", self.limb, i, || Value::known(F::from(i as u64)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Two-player showdown: evaluates each player's best hand against a
+/// shared board and proves the winner without revealing either player's
+/// `hand_score` beyond what each hand's own config already exposes.
+///
+/// Both hands' community-card instance columns are assigned the same
+/// board values; they aren't deduplicated into a single shared instance
+/// column yet, so the public input vector currently carries the board
+/// twice. A verifier wiring this up should check both copies agree.
+#[derive(Debug, Clone)]
+pub struct ShowdownConfig {
+    pub hand_a: VanillaHoldemConfig,
+    pub hand_b: VanillaHoldemConfig,
+    pub q_compare: Selector,
+    pub score_a: Column<Advice>,
+    pub score_b: Column<Advice>,
+    pub sign: Column<Advice>,
+    pub abs_diff: Column<Advice>,
+    pub diff_limbs: Vec<Column<Advice>>,
+    pub tie_inv: Column<Advice>,
+    pub is_tie: Column<Advice>,
+    pub winner: Column<Advice>,
+    pub winner_instance: Column<Instance>,
+    pub score_limb_table: ScoreLimbTableConfig,
+}
+
+struct ShowdownChip<F: FieldExt> {
+    config: ShowdownConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> ShowdownChip<F> {
+    pub fn construct(config: ShowdownConfig) -> Self {
+        Self { config, _marker: PhantomData }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, params: HoldemParams) -> ShowdownConfig {
+        let card_table = CardTableConfig::configure(meta);
+        let range_table = RangeTableConfig::configure(meta);
+        let card_gap_table = CardGapTableConfig::configure(meta);
+        let hand_a = VanillaHoldemChip::configure_columns(meta, params, card_table, range_table, card_gap_table);
+        let hand_b = VanillaHoldemChip::configure_columns(meta, params, card_table, range_table, card_gap_table);
+
+        let q_compare = meta.selector();
+        let score_a = meta.advice_column();
+        let score_b = meta.advice_column();
+        meta.enable_equality(score_a);
+        meta.enable_equality(score_b);
+        let sign = meta.advice_column();
+        let abs_diff = meta.advice_column();
+        let diff_limbs = (0..2).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let tie_inv = meta.advice_column();
+        let is_tie = meta.advice_column();
+        let winner = meta.advice_column();
+        meta.enable_equality(winner);
+        let winner_instance = meta.instance_column();
+        let score_limb_table = ScoreLimbTableConfig::configure(meta);
+
+        for &limb in &diff_limbs {
+            meta.lookup("score diff limb is within its range", |meta| {
+                let q_compare = meta.query_selector(q_compare);
+                let limb = meta.query_advice(limb, Rotation::cur());
+                vec![(q_compare * limb, score_limb_table.limb)]
+            });
+        }
+
+        meta.create_gate("showdown comparison", |meta| {
+            let q_compare = meta.query_selector(q_compare);
+
+            let score_a = meta.query_advice(score_a, Rotation::cur());
+            let score_b = meta.query_advice(score_b, Rotation::cur());
+            let sign = meta.query_advice(sign, Rotation::cur());
+            let abs_diff = meta.query_advice(abs_diff, Rotation::cur());
+            let tie_inv = meta.query_advice(tie_inv, Rotation::cur());
+            let is_tie = meta.query_advice(is_tie, Rotation::cur());
+            let winner = meta.query_advice(winner, Rotation::cur());
+
+            let diff = score_a - score_b;
+
+            let mut constraints = vec![];
+
+            // sign is boolean: 1 means score_a >= score_b.
+            constraints.push(q_compare.clone() * (sign.clone() * (Expression::Constant(F::one()) - sign.clone())));
+
+            // abs_diff = diff if sign == 1, else -diff. If a prover claims
+            // the wrong sign, abs_diff becomes diff's negation modulo the
+            // field, which is huge and fails the limb range check below.
+            let signed = sign.clone() * Expression::Constant(F::from(2)) - Expression::Constant(F::one());
+            constraints.push(q_compare.clone() * (abs_diff.clone() - diff.clone() * signed));
+
+            // abs_diff decomposes into two SCORE_LIMB_BITS-wide limbs,
+            // each bounded by the lookup above.
+            let limb0 = meta.query_advice(diff_limbs[0], Rotation::cur());
+            let limb1 = meta.query_advice(diff_limbs[1], Rotation::cur());
+            let limb_base = Expression::Constant(F::from(1u64 << SCORE_LIMB_BITS));
+            constraints.push(q_compare.clone() * (abs_diff - (limb0 + limb1 * limb_base)));
+
+            // is_tie is the standard is_zero(diff) witness.
+            constraints.push(
+                q_compare.clone()
+                    * (is_tie.clone() - (Expression::Constant(F::one()) - diff.clone() * tie_inv)),
+            );
+            constraints.push(q_compare.clone() * (diff * is_tie.clone()));
+
+            // winner = 2 when tied, else 1 - sign (0 = A wins, 1 = B wins).
+            let not_tie_winner = Expression::Constant(F::one()) - sign;
+            let expected_winner = is_tie.clone() * Expression::Constant(F::from(2))
+                + (Expression::Constant(F::one()) - is_tie) * not_tie_winner;
+            constraints.push(q_compare * (winner - expected_winner));
+
+            constraints
+        });
+
+        ShowdownConfig {
+            hand_a,
+            hand_b,
+            q_compare,
+            score_a,
+            score_b,
+            sign,
+            abs_diff,
+            diff_limbs,
+            tie_inv,
+            is_tie,
+            winner,
+            winner_instance,
+            score_limb_table,
+        }
+    }
+
+    /// `hand_a` and `hand_b` were configured against the same
+    /// `card_table`/`range_table`/`card_gap_table` (see `configure`), so
+    /// loading either one's copy loads both.
+    pub fn load_tables(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        self.config.hand_a.card_table.load(layouter)?;
+        self.config.hand_a.range_table.load(layouter)?;
+        self.config.hand_a.card_gap_table.load(layouter)?;
+        self.config.score_limb_table.load(layouter)?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn assign_showdown(
+        &self,
+        mut layouter: impl Layouter<F>,
+        hole_a: &[Value<Assigned<F>>],
+        hole_b: &[Value<Assigned<F>>],
+        board: &[Value<Assigned<F>>],
+    ) -> Result<(), Error> {
+        let chip_a = VanillaHoldemChip::construct(self.config.hand_a.clone());
+        let chip_b = VanillaHoldemChip::construct(self.config.hand_b.clone());
+
+        let (score_a_cell, score_a_raw) =
+            chip_a.assign_card_scored(layouter.namespace(|| "player a hand"), hole_a, board)?;
+        let (score_b_cell, score_b_raw) =
+            chip_b.assign_card_scored(layouter.namespace(|| "player b hand"), hole_b, board)?;
+
+        let score_a_val = score_a_cell.value().map(|v| *v);
+        let score_b_val = score_b_cell.value().map(|v| *v);
+
+        layouter.assign_region(
+            || "showdown comparison",
+            |mut region| {
+                self.config.q_compare.enable(&mut region, 0)?;
+
+                let score_a_copy = region.assign_advice(|| "score_a", self.config.score_a, 0, || score_a_val)?;
+                region.constrain_equal(score_a_cell.cell(), score_a_copy.cell())?;
+                let score_b_copy = region.assign_advice(|| "score_b", self.config.score_b, 0, || score_b_val)?;
+                region.constrain_equal(score_b_cell.cell(), score_b_copy.cell())?;
+
+                let diff = score_a_val - score_b_val;
+
+                // sign/abs_diff are derived from the plain u64 scores
+                // (tracked alongside the field cells by `assign_card_scored`)
+                // rather than from the field values directly, since field
+                // elements have no canonical notion of sign.
+                let a_wins = score_a_raw.zip(score_b_raw).map(|(a, b)| a >= b);
+                let sign = a_wins.map(|a_wins| if a_wins { F::one() } else { F::zero() });
+                region.assign_advice(|| "sign", self.config.sign, 0, || sign)?;
+
+                let signed = sign.map(|s| s * F::from(2) - F::one());
+                let abs_diff = diff * signed;
+                region.assign_advice(|| "abs_diff", self.config.abs_diff, 0, || abs_diff)?;
+
+                let abs_diff_raw = score_a_raw
+                    .zip(score_b_raw)
+                    .map(|(a, b)| if a >= b { a - b } else { b - a });
+                let limb_base = 1u64 << SCORE_LIMB_BITS;
+                let limb0 = abs_diff_raw.map(|v| F::from(v % limb_base));
+                let limb1 = abs_diff_raw.map(|v| F::from(v / limb_base));
+                region.assign_advice(|| "diff_limb_0", self.config.diff_limbs[0], 0, || limb0)?;
+                region.assign_advice(|| "diff_limb_1", self.config.diff_limbs[1], 0, || limb1)?;
+
+                let tie_inv = diff.map(|d| d.invert().unwrap_or(F::zero()));
+                let is_tie = Value::known(F::one()) - diff * tie_inv;
+                region.assign_advice(|| "tie_inv", self.config.tie_inv, 0, || tie_inv)?;
+                region.assign_advice(|| "is_tie", self.config.is_tie, 0, || is_tie)?;
+
+                let winner = is_tie * Value::known(F::from(2))
+                    + (Value::known(F::one()) - is_tie) * (Value::known(F::one()) - sign);
+                let winner_cell = region.assign_advice(|| "winner", self.config.winner, 0, || winner)?;
+                region.constrain_instance(winner_cell.cell(), self.config.winner_instance, 0)?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct ShowdownCircuit<F: FieldExt> {
+    pub params: HoldemParams,
+    pub hole_a: Vec<Value<Assigned<F>>>,
+    pub hole_b: Vec<Value<Assigned<F>>>,
+    pub board: Vec<Value<Assigned<F>>>,
+}
+
+impl<F: FieldExt> Default for ShowdownCircuit<F> {
+    fn default() -> Self {
+        let params = HoldemParams::default();
+        Self {
+            params,
+            hole_a: vec![Value::unknown(); params.n_hole],
+            hole_b: vec![Value::unknown(); params.n_hole],
+            board: vec![Value::unknown(); params.n_board],
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ShowdownCircuit<F> {
+    type Config = ShowdownConfig;
+    type FloorPlanner = V1;
+    type Params = HoldemParams;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            params: self.params,
+            hole_a: vec![Value::unknown(); self.params.n_hole],
+            hole_b: vec![Value::unknown(); self.params.n_hole],
+            board: vec![Value::unknown(); self.params.n_board],
+        }
+    }
+
+    fn params(&self) -> Self::Params {
+        self.params
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::configure_with_params(meta, HoldemParams::default())
+    }
+
+    fn configure_with_params(meta: &mut ConstraintSystem<F>, params: Self::Params) -> Self::Config {
+        ShowdownChip::configure(meta, params)
+    }
+
+    fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        let chip = ShowdownChip::construct(config);
+
+        chip.load_tables(&mut layouter)?;
+
+        chip.assign_showdown(
+            layouter.namespace(|| "showdown"),
+            &self.hole_a,
+            &self.hole_b,
+            &self.board,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::dev::MockProver;
+    use pasta_curves::Fp;
+
+    // k=10 comfortably covers VanillaHoldemCircuit's largest table
+    // (card_gap_table, 52 rows) plus blinding rows.
+    const HOLDEM_K: u32 = 10;
+
+    // Board: 2s 7d 9c Jh Ks -- no pair, straight or flush of its own.
+    const BOARD: [u64; 5] = [0, 18, 33, 48, 11];
+    // k=13 covers ShowdownCircuit's score_limb_table (2^SCORE_LIMB_BITS =
+    // 4096 rows) plus blinding rows.
+    const SHOWDOWN_K: u32 = 13;
+
+    // Pocket aces: a pair of aces plus K, J, 9 kickers.
+    const HOLE_A: [u64; 2] = [12, 25];
+    const SCORE_A: u64 = 1_837_975;
+    // Pocket threes: a weaker pair with the same kickers -- loses to
+    // HOLE_A on the paired rank.
+    const HOLE_B: [u64; 2] = [27, 14];
+    const SCORE_B: u64 = 1_117_079;
+
+    fn card(index: u64) -> Value<Assigned<Fp>> {
+        Value::known(Assigned::from(Fp::from(index)))
+    }
+
+    /// Builds a raw card index (0..52) from a suit (0..4) and rank (0..13,
+    /// "2" through "A"), matching `card = suit * 13 + rank`.
+    fn card_index(suit: u64, rank: u64) -> u64 {
+        suit * 13 + rank
+    }
+
+    fn holdem_circuit(hole: &[u64], board: &[u64]) -> VanillaHoldemCircuit<Fp> {
+        VanillaHoldemCircuit {
+            params: HoldemParams::texas_holdem(),
+            cards: hole.iter().map(|&c| card(c)).collect(),
+            table_cards: board.iter().map(|&c| card(c)).collect(),
+        }
+    }
+
+    fn holdem_instance(board: &[u64], hand_score: u64) -> Vec<Vec<Fp>> {
+        board
+            .iter()
+            .map(|&c| vec![Fp::from(c)])
+            .chain(std::iter::once(vec![Fp::from(hand_score)]))
+            .collect()
+    }
+
+    #[test]
+    fn valid_hand_is_satisfied() {
+        let circuit = holdem_circuit(&HOLE_A, &BOARD);
+        let instance = holdem_instance(&BOARD, SCORE_A);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    /// Finding: `pool_cards` must be pairwise distinct, or a prover could
+    /// claim a duplicate card to inflate `hand_score`'s category past
+    /// `W_ROYAL_FLUSH`. Here the second hole card collides with the
+    /// board's K♠ (card 11), so the pool is only 6 distinct cards.
+    #[test]
+    fn duplicate_pool_card_is_rejected() {
+        let hole = [HOLE_A[0], BOARD[4]];
+        let circuit = holdem_circuit(&hole, &BOARD);
+        let instance = holdem_instance(&BOARD, 0);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Finding: the board positions of `pool_cards` must be constrained
+    /// against the public `table_cards` instance, or a prover could prove
+    /// against a self-chosen board while publishing a different one.
+    #[test]
+    fn board_instance_mismatch_is_rejected() {
+        let circuit = holdem_circuit(&HOLE_A, &BOARD);
+        let mut published_board = BOARD;
+        published_board[0] = 7;
+        let instance = holdem_instance(&published_board, SCORE_A);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Finding: `valid_hand_is_satisfied` only exercises one pair. Each
+    /// test below gives a 2-hole/5-board pool whose best-5-of-7 hand is a
+    /// different category, with two low, unrelated "filler" board cards
+    /// that can't combine into anything stronger. Expected `hand_score`
+    /// values are computed independently from the category/tiebreak rules
+    /// (not by calling `evaluate_five`), so they'd catch a circuit/gate
+    /// mismatch the same way `SCORE_A`/`SCORE_B` above do.
+
+    #[test]
+    fn straight_hand_is_satisfied() {
+        // 8,9,10,J,Q of mixed suits; 2,3 filler don't extend the run.
+        let hole = [card_index(0, 6), card_index(1, 10)];
+        let board = [
+            card_index(2, 7),
+            card_index(3, 8),
+            card_index(0, 9),
+            card_index(1, 0),
+            card_index(2, 1),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 4_888_694);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn flush_hand_is_satisfied() {
+        // 2,4,6,8,10 of one suit, non-consecutive; filler on other suits.
+        let hole = [card_index(1, 0), card_index(1, 2)];
+        let board = [
+            card_index(1, 4),
+            card_index(1, 6),
+            card_index(1, 8),
+            card_index(0, 1),
+            card_index(2, 3),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 5_792_800);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn two_pair_hand_is_satisfied() {
+        // Pair of 9s, pair of 5s, king kicker; 2, 4 filler.
+        let hole = [card_index(0, 7), card_index(1, 7)];
+        let board = [
+            card_index(2, 3),
+            card_index(3, 3),
+            card_index(0, 11),
+            card_index(1, 0),
+            card_index(2, 2),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 2_571_008);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn three_of_a_kind_hand_is_satisfied() {
+        // Trip 7s plus 3, 4, 6, J kickers (best 5 of 7 keeps J and 6).
+        let hole = [card_index(0, 5), card_index(1, 5)];
+        let board = [
+            card_index(2, 5),
+            card_index(3, 9),
+            card_index(0, 1),
+            card_index(1, 2),
+            card_index(2, 4),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 3_511_296);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn four_of_a_kind_hand_is_satisfied() {
+        // Quad 8s plus a queen kicker; 2, 3 filler.
+        let hole = [card_index(0, 6), card_index(1, 6)];
+        let board = [
+            card_index(2, 6),
+            card_index(3, 6),
+            card_index(0, 10),
+            card_index(1, 0),
+            card_index(2, 1),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 7_774_208);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn full_house_hand_is_satisfied() {
+        // Trip 10s over a pair of 4s; 2, 3 filler.
+        let hole = [card_index(0, 8), card_index(1, 8)];
+        let board = [
+            card_index(2, 8),
+            card_index(3, 2),
+            card_index(0, 2),
+            card_index(1, 0),
+            card_index(2, 1),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 6_823_936);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn straight_flush_hand_is_satisfied() {
+        // 6,7,8,9,10 of one suit (not ace-high, so not a royal flush).
+        let hole = [card_index(0, 4), card_index(0, 5)];
+        let board = [
+            card_index(0, 6),
+            card_index(0, 7),
+            card_index(0, 8),
+            card_index(1, 0),
+            card_index(2, 1),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 8_943_188);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn royal_flush_hand_is_satisfied() {
+        // 10,J,Q,K,A of one suit.
+        let hole = [card_index(0, 8), card_index(0, 9)];
+        let board = [
+            card_index(0, 10),
+            card_index(0, 11),
+            card_index(0, 12),
+            card_index(1, 0),
+            card_index(2, 1),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 10_271_384);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    /// Finding: the wheel (A-2-3-4-5) is poker's weakest straight, but
+    /// `sorted` holds the ace at raw rank 12; this hand's score must
+    /// still land below a 6-high straight's (4,469,264), not above every
+    /// other straight.
+    #[test]
+    fn wheel_hand_is_satisfied() {
+        let hole = [card_index(0, 12), card_index(1, 0)];
+        let board = [
+            card_index(2, 1),
+            card_index(3, 2),
+            card_index(0, 3),
+            card_index(1, 7),
+            card_index(2, 9),
+        ];
+        let circuit = holdem_circuit(&hole, &board);
+        let instance = holdem_instance(&board, 4_399_360);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+        assert!(4_399_360 < 4_469_264, "wheel must score below a 6-high straight");
+    }
+
+    /// Finding: a prover publishing a `hand_score` for a stronger category
+    /// than the hand actually has (here: claiming royal flush on
+    /// `HOLE_A`'s one pair) must be rejected.
+    #[test]
+    fn wrong_category_score_is_rejected() {
+        let circuit = holdem_circuit(&HOLE_A, &BOARD);
+        let instance = holdem_instance(&BOARD, 10_271_384);
+        let prover = MockProver::run(HOLDEM_K, &circuit, instance).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn showdown_circuit() -> ShowdownCircuit<Fp> {
+        ShowdownCircuit {
+            params: HoldemParams::texas_holdem(),
+            hole_a: HOLE_A.iter().map(|&c| card(c)).collect(),
+            hole_b: HOLE_B.iter().map(|&c| card(c)).collect(),
+            board: BOARD.iter().map(|&c| card(c)).collect(),
+        }
+    }
+
+    fn showdown_instance(winner: u64) -> Vec<Vec<Fp>> {
+        let mut instance = holdem_instance(&BOARD, SCORE_A);
+        instance.extend(holdem_instance(&BOARD, SCORE_B));
+        instance.push(vec![Fp::from(winner)]);
+        instance
+    }
+
+    #[test]
+    fn showdown_picks_the_stronger_hand() {
+        let circuit = showdown_circuit();
+        let instance = showdown_instance(0);
+        let prover = MockProver::run(SHOWDOWN_K, &circuit, instance).unwrap();
+        prover.assert_satisfied();
+    }
+
+    /// Finding: the showdown's `winner` must be tied to `winner_instance`,
+    /// or a prover could publish a winner that doesn't match the hands it
+    /// actually proved.
+    #[test]
+    fn showdown_rejects_wrong_winner() {
+        let circuit = showdown_circuit();
+        let instance = showdown_instance(1);
+        let prover = MockProver::run(SHOWDOWN_K, &circuit, instance).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}